@@ -0,0 +1,34 @@
+//! CRC-32 checksum as used by the PNG chunk format (ISO 3309 / ITU-T V.42).
+
+fn make_table() -> [u32; 256] {
+    let mut table = [0u32; 256];
+
+    for (n, entry) in table.iter_mut().enumerate() {
+        let mut c = n as u32;
+
+        for _ in 0..8 {
+            if c & 1 != 0 {
+                c = 0xedb88320 ^ (c >> 1);
+            } else {
+                c >>= 1;
+            }
+        }
+
+        *entry = c;
+    }
+
+    table
+}
+
+/// Compute the CRC-32 of `data`, as required for the trailing checksum of
+/// every PNG chunk.
+pub fn crc32(data: &[u8]) -> u32 {
+    let table = make_table();
+    let mut c: u32 = 0xffffffff;
+
+    for &byte in data {
+        c = table[((c ^ byte as u32) & 0xff) as usize] ^ (c >> 8);
+    }
+
+    c ^ 0xffffffff
+}
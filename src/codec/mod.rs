@@ -0,0 +1,5 @@
+//! Image file format codecs.
+
+mod crc32;
+mod zlib;
+pub mod png;
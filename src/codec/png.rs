@@ -0,0 +1,474 @@
+//! Pure-Rust PNG codec for [`Image`](crate::image::Image).
+//!
+//! Supports the non-interlaced grayscale, RGB, palette, grayscale+alpha and
+//! RGBA color types at 8 and 16 bits per sample. Palette and grayscale+alpha
+//! images are expanded into RGB/RGBA on load since [`ColorType`] only has
+//! `Mono`/`RGB`/`RGBA` variants. `tRNS` transparency and interlacing are not
+//! supported.
+
+use std::fs;
+use std::path::Path;
+
+use crate::codec::crc32::crc32;
+use crate::codec::zlib;
+use crate::image::{ColorType, Image};
+
+const SIGNATURE: [u8; 8] = [137, 80, 78, 71, 13, 10, 26, 10];
+
+enum Samples {
+    Eight(Vec<u8>),
+    Sixteen(Vec<u16>)
+}
+
+struct Decoded {
+    width: u32,
+    height: u32,
+    color_type: ColorType,
+    samples: Samples
+}
+
+fn read_chunks(bytes: &[u8]) -> Result<Vec<(String, Vec<u8>)>, String> {
+    if bytes.len() < 8 || bytes[0..8] != SIGNATURE {
+        return Err("PNG: missing or invalid file signature".to_string());
+    }
+
+    let mut chunks = Vec::new();
+    let mut pos = 8;
+
+    while pos + 8 <= bytes.len() {
+        let length = u32::from_be_bytes([
+            bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]
+        ]) as usize;
+        pos += 4;
+
+        let ctype = bytes[pos..pos + 4].to_vec();
+        let ctype_str = String::from_utf8_lossy(&ctype).to_string();
+        pos += 4;
+
+        if pos + length + 4 > bytes.len() {
+            return Err(format!("PNG: truncated {} chunk", ctype_str));
+        }
+
+        let data = bytes[pos..pos + length].to_vec();
+        pos += length;
+
+        let stored_crc = u32::from_be_bytes([
+            bytes[pos], bytes[pos + 1], bytes[pos + 2], bytes[pos + 3]
+        ]);
+        pos += 4;
+
+        let mut crc_input = ctype.clone();
+        crc_input.extend_from_slice(&data);
+        if crc32(&crc_input) != stored_crc {
+            return Err(format!("PNG: CRC mismatch in {} chunk", ctype_str));
+        }
+
+        let is_end = ctype_str == "IEND";
+        chunks.push((ctype_str, data));
+
+        if is_end { break; }
+    }
+
+    Ok(chunks)
+}
+
+fn paeth(a: i16, b: i16, c: i16) -> u8 {
+    let p = a + b - c;
+    let pa = (p - a).abs();
+    let pb = (p - b).abs();
+    let pc = (p - c).abs();
+
+    if pa <= pb && pa <= pc { a as u8 }
+    else if pb <= pc { b as u8 }
+    else { c as u8 }
+}
+
+fn unfilter(raw: &[u8], width: usize, height: usize, bpp: usize) -> Result<Vec<u8>, String> {
+    let stride = width * bpp;
+    let mut out = vec![0u8; stride * height];
+
+    let mut pos = 0;
+    for row in 0..height {
+        if pos >= raw.len() {
+            return Err("PNG: truncated scanline data".to_string());
+        }
+
+        let filter_type = raw[pos];
+        pos += 1;
+
+        if pos + stride > raw.len() {
+            return Err("PNG: truncated scanline data".to_string());
+        }
+
+        let row_start = row * stride;
+        let prev_start = if row > 0 { (row - 1) * stride } else { 0 };
+
+        for i in 0..stride {
+            let x = raw[pos + i];
+
+            let a = if i >= bpp { out[row_start + i - bpp] as i16 } else { 0 };
+            let b = if row > 0 { out[prev_start + i] as i16 } else { 0 };
+            let c = if row > 0 && i >= bpp { out[prev_start + i - bpp] as i16 } else { 0 };
+
+            out[row_start + i] = match filter_type {
+                0 => x,
+                1 => x.wrapping_add(a as u8),
+                2 => x.wrapping_add(b as u8),
+                3 => x.wrapping_add(((a + b) / 2) as u8),
+                4 => x.wrapping_add(paeth(a, b, c)),
+                _ => return Err(format!("PNG: unsupported filter type {}", filter_type))
+            };
+        }
+
+        pos += stride;
+    }
+
+    Ok(out)
+}
+
+fn decode<P: AsRef<Path>>(path: P) -> Result<Decoded, String> {
+    let bytes = fs::read(path).map_err(|e| format!("PNG: could not read file: {}", e))?;
+    let chunks = read_chunks(&bytes)?;
+
+    let ihdr = &chunks.iter().find(|(t, _)| t == "IHDR")
+        .ok_or_else(|| "PNG: missing IHDR chunk".to_string())?.1;
+
+    if ihdr.len() < 13 {
+        return Err("PNG: malformed IHDR chunk".to_string());
+    }
+
+    let width = u32::from_be_bytes([ihdr[0], ihdr[1], ihdr[2], ihdr[3]]);
+    let height = u32::from_be_bytes([ihdr[4], ihdr[5], ihdr[6], ihdr[7]]);
+    let bit_depth = ihdr[8];
+    let color_type_byte = ihdr[9];
+    let compression = ihdr[10];
+    let filter_method = ihdr[11];
+    let interlace = ihdr[12];
+
+    if compression != 0 || filter_method != 0 {
+        return Err("PNG: unsupported compression/filter method".to_string());
+    }
+    if interlace != 0 {
+        return Err("PNG: interlaced PNGs are not supported".to_string());
+    }
+    if bit_depth != 8 && bit_depth != 16 {
+        return Err(format!("PNG: unsupported bit depth {}", bit_depth));
+    }
+    if color_type_byte == 3 && bit_depth != 8 {
+        return Err("PNG: palette images must be 8 bits per sample".to_string());
+    }
+
+    let src_channels: usize = match color_type_byte {
+        0 => 1,
+        2 => 3,
+        3 => 1,
+        4 => 2,
+        6 => 4,
+        _ => return Err(format!("PNG: unsupported color type {}", color_type_byte))
+    };
+
+    let palette = chunks.iter().find(|(t, _)| t == "PLTE").map(|(_, d)| d.clone());
+
+    let mut idat = Vec::new();
+    for (ctype, data) in &chunks {
+        if ctype == "IDAT" {
+            idat.extend_from_slice(data);
+        }
+    }
+    if idat.is_empty() {
+        return Err("PNG: missing IDAT data".to_string());
+    }
+
+    let decompressed = zlib::inflate(&idat)?;
+
+    let bytes_per_sample = if bit_depth == 16 { 2 } else { 1 };
+    let bpp = src_channels * bytes_per_sample;
+    let raw = unfilter(&decompressed, width as usize, height as usize, bpp)?;
+
+    let pixel_count = (width as usize) * (height as usize);
+
+    if bit_depth == 16 {
+        let mut samples = vec![0u16; pixel_count * src_channels];
+        for i in 0..samples.len() {
+            samples[i] = u16::from_be_bytes([raw[i * 2], raw[i * 2 + 1]]);
+        }
+
+        let (color_type, out_samples) = expand_color_type_u16(color_type_byte, &samples)?;
+        Ok(Decoded { width, height, color_type, samples: Samples::Sixteen(out_samples) })
+    }
+    else {
+        let (color_type, out_samples) =
+            expand_color_type_u8(color_type_byte, &raw, palette.as_deref())?;
+        Ok(Decoded { width, height, color_type, samples: Samples::Eight(out_samples) })
+    }
+}
+
+/// Expand 8-bit palette and grayscale+alpha samples into the Mono/RGB/RGBA
+/// space that [`ColorType`] supports, leaving grayscale and RGB/RGBA as-is.
+fn expand_color_type_u8(
+    color_type_byte: u8, samples: &[u8], palette: Option<&[u8]>
+) -> Result<(ColorType, Vec<u8>), String> {
+    match color_type_byte {
+        0 => Ok((ColorType::Mono, samples.to_vec())),
+        2 => Ok((ColorType::RGB, samples.to_vec())),
+        6 => Ok((ColorType::RGBA, samples.to_vec())),
+        4 => Ok((ColorType::RGBA, expand_gray_alpha(samples))),
+        3 => {
+            let palette = palette
+                .ok_or_else(|| "PNG: palette image missing PLTE chunk".to_string())?;
+
+            let mut out = Vec::with_capacity(samples.len() * 3);
+            for &index in samples {
+                let base = index as usize * 3;
+
+                if base + 2 >= palette.len() {
+                    return Err("PNG: palette index out of range".to_string());
+                }
+
+                out.push(palette[base]);
+                out.push(palette[base + 1]);
+                out.push(palette[base + 2]);
+            }
+            Ok((ColorType::RGB, out))
+        },
+        _ => Err(format!("PNG: unsupported color type {}", color_type_byte))
+    }
+}
+
+/// Expand 16-bit grayscale+alpha samples into RGBA; palette images are
+/// always 8 bits per sample so they never reach this function.
+fn expand_color_type_u16(
+    color_type_byte: u8, samples: &[u16]
+) -> Result<(ColorType, Vec<u16>), String> {
+    match color_type_byte {
+        0 => Ok((ColorType::Mono, samples.to_vec())),
+        2 => Ok((ColorType::RGB, samples.to_vec())),
+        6 => Ok((ColorType::RGBA, samples.to_vec())),
+        4 => Ok((ColorType::RGBA, expand_gray_alpha(samples))),
+        3 => Err("PNG: palette images must be 8 bits per sample".to_string()),
+        _ => Err(format!("PNG: unsupported color type {}", color_type_byte))
+    }
+}
+
+fn expand_gray_alpha<T: Copy>(samples: &[T]) -> Vec<T> {
+    let mut out = Vec::with_capacity(samples.len() * 2);
+    for pair in samples.chunks_exact(2) {
+        out.push(pair[0]);
+        out.push(pair[0]);
+        out.push(pair[0]);
+        out.push(pair[1]);
+    }
+    out
+}
+
+fn write_chunk(out: &mut Vec<u8>, ctype: &[u8; 4], data: &[u8]) {
+    out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    out.extend_from_slice(ctype);
+    out.extend_from_slice(data);
+
+    let mut crc_input = ctype.to_vec();
+    crc_input.extend_from_slice(data);
+    out.extend_from_slice(&crc32(&crc_input).to_be_bytes());
+}
+
+fn color_type_byte(color_type: &ColorType) -> u8 {
+    match color_type {
+        ColorType::Mono => 0,
+        ColorType::RGB => 2,
+        ColorType::RGBA => 6
+    }
+}
+
+fn encode(
+    path: impl AsRef<Path>, width: u32, height: u32, bit_depth: u8,
+    color_type: &ColorType, raw_scanlines: Vec<u8>
+) -> Result<(), String> {
+    let mut out = Vec::new();
+    out.extend_from_slice(&SIGNATURE);
+
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    ihdr.push(bit_depth);
+    ihdr.push(color_type_byte(color_type));
+    ihdr.push(0); // compression method
+    ihdr.push(0); // filter method
+    ihdr.push(0); // interlace method
+    write_chunk(&mut out, b"IHDR", &ihdr);
+
+    let idat = zlib::deflate_stored(&raw_scanlines);
+    write_chunk(&mut out, b"IDAT", &idat);
+
+    write_chunk(&mut out, b"IEND", &[]);
+
+    fs::write(path, out).map_err(|e| format!("PNG: could not write file: {}", e))
+}
+
+impl Image<u8> {
+    /// Load an 8-bit-per-channel image from a PNG file.
+    ///
+    /// 16-bit PNGs are down-sampled to 8 bits by keeping the high byte of
+    /// each sample; palette and grayscale+alpha PNGs are expanded to RGB and
+    /// RGBA respectively.
+    pub fn load_png<P: AsRef<Path>>(path: P) -> Result<Image<u8>, String> {
+        let decoded = decode(path)?;
+
+        let data = match decoded.samples {
+            Samples::Eight(v) => v,
+            Samples::Sixteen(v) => v.into_iter().map(|s| (s >> 8) as u8).collect()
+        };
+
+        let depth = match &decoded.color_type {
+            ColorType::Mono => 1,
+            ColorType::RGB => 3,
+            ColorType::RGBA => 4
+        };
+
+        Ok(Image::from_raw(decoded.width, decoded.height, depth, decoded.color_type, data))
+    }
+
+    /// Save this image as an 8-bit-per-channel PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let channels = self.depth() as usize;
+        let cols = self.cols() as usize;
+        let stride = cols * channels;
+
+        let mut raw = Vec::with_capacity((stride + 1) * self.rows() as usize);
+        for row in self.data().chunks_exact(stride) {
+            raw.push(0); // "None" filter
+            raw.extend_from_slice(row);
+        }
+
+        encode(path, self.cols(), self.rows(), 8, self.color_type(), raw)
+    }
+}
+
+impl Image<u16> {
+    /// Load a 16-bit-per-channel image from a PNG file.
+    ///
+    /// 8-bit PNGs are up-sampled to 16 bits by replicating each byte
+    /// (`v * 0x101`) so the full `u16` range is used; palette and
+    /// grayscale+alpha PNGs are expanded to RGB and RGBA respectively.
+    pub fn load_png<P: AsRef<Path>>(path: P) -> Result<Image<u16>, String> {
+        let decoded = decode(path)?;
+
+        let data = match decoded.samples {
+            Samples::Sixteen(v) => v,
+            Samples::Eight(v) => v.into_iter().map(|s| s as u16 * 0x101).collect()
+        };
+
+        let depth = match &decoded.color_type {
+            ColorType::Mono => 1,
+            ColorType::RGB => 3,
+            ColorType::RGBA => 4
+        };
+
+        Ok(Image::from_raw(decoded.width, decoded.height, depth, decoded.color_type, data))
+    }
+
+    /// Save this image as a 16-bit-per-channel PNG file.
+    pub fn save_png<P: AsRef<Path>>(&self, path: P) -> Result<(), String> {
+        let channels = self.depth() as usize;
+        let cols = self.cols() as usize;
+        let stride = cols * channels;
+
+        let mut raw = Vec::with_capacity((stride * 2 + 1) * self.rows() as usize);
+        for row in self.data().chunks_exact(stride) {
+            raw.push(0); // "None" filter
+            for &sample in row {
+                raw.extend_from_slice(&sample.to_be_bytes());
+            }
+        }
+
+        encode(path, self.cols(), self.rows(), 16, self.color_type(), raw)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::image::ColorType;
+    use crate::mono_image;
+
+    fn scratch_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("cvrs_png_test_{}_{}.png", std::process::id(), name))
+    }
+
+    #[test]
+    fn u8_round_trips_through_save_and_load() {
+        let path = scratch_path("u8_round_trip");
+        let img = mono_image![
+            10, 20, 30;
+            40, 50, 60
+        ];
+
+        img.save_png(&path).unwrap();
+        let loaded = crate::image::Image::<u8>::load_png(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.cols(), 3);
+        assert_eq!(loaded.rows(), 2);
+        assert_eq!(*loaded.color_type(), ColorType::Mono);
+        assert_eq!(loaded.get_pixel_data(2, 1).unwrap(), vec![60]);
+    }
+
+    #[test]
+    fn u16_round_trips_through_save_and_load() {
+        let path = scratch_path("u16_round_trip");
+
+        let mut img = crate::image::Image::<u16>::new(2, 2, ColorType::RGB);
+        img.set_pixel_data(0, 0, vec![0, 100, 1000]).unwrap();
+        img.set_pixel_data(1, 0, vec![2000, 3000, 4000]).unwrap();
+        img.set_pixel_data(0, 1, vec![5000, 6000, 7000]).unwrap();
+        img.set_pixel_data(1, 1, vec![8000, 9000, 65535]).unwrap();
+
+        img.save_png(&path).unwrap();
+        let loaded = crate::image::Image::<u16>::load_png(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.cols(), 2);
+        assert_eq!(loaded.rows(), 2);
+        assert_eq!(*loaded.color_type(), ColorType::RGB);
+        assert_eq!(loaded.get_pixel_data(1, 1).unwrap(), vec![8000, 9000, 65535]);
+    }
+
+    /// A real (non-stored) zlib-compressed, 4x4 grayscale PNG whose scanlines
+    /// cycle through the None, Sub, Up and Average filter types, produced
+    /// externally with Python's `zlib.compress` to exercise the decoder
+    /// against a genuine Huffman-coded stream rather than our own encoder.
+    const FILTERED_PNG: [u8; 85] = [
+        137, 80, 78, 71, 13, 10, 26, 10, 0, 0, 0, 13, 73, 72, 68, 82, 0, 0, 0, 4, 0, 0, 0, 4, 8, 0,
+        0, 0, 0, 140, 154, 193, 162, 0, 0, 0, 28, 73, 68, 65, 84, 120, 218, 99, 224, 18, 145, 211,
+        96, 228, 231, 226, 226, 98, 250, 203, 252, 239, 15, 51, 51, 35, 43, 55, 0, 31, 31, 3, 166,
+        78, 54, 176, 190, 0, 0, 0, 0, 73, 69, 78, 68, 174, 66, 96, 130
+    ];
+
+    #[test]
+    fn decodes_externally_produced_filtered_png() {
+        let path = scratch_path("filtered_external");
+        std::fs::write(&path, FILTERED_PNG).unwrap();
+
+        let loaded = crate::image::Image::<u8>::load_png(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.cols(), 4);
+        assert_eq!(loaded.rows(), 4);
+        assert_eq!(*loaded.color_type(), ColorType::Mono);
+
+        let expected = [
+            [10, 20, 30, 40],
+            [15, 25, 35, 45],
+            [12, 28, 33, 41],
+            [9, 19, 31, 47]
+        ];
+
+        for (row, values) in expected.iter().enumerate() {
+            for (col, &val) in values.iter().enumerate() {
+                assert_eq!(
+                    loaded.get_pixel_data(col as u32, row as u32).unwrap(), vec![val],
+                    "mismatch at (col {}, row {})", col, row
+                );
+            }
+        }
+    }
+}
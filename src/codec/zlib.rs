@@ -0,0 +1,355 @@
+//! Minimal pure-Rust DEFLATE (RFC 1951) / zlib (RFC 1950) implementation.
+//!
+//! Only what `codec::png` needs is provided: an `inflate` able to decode
+//! stored, fixed-Huffman and dynamic-Huffman blocks, and a `deflate_stored`
+//! encoder that only ever emits stored (uncompressed) blocks. The latter
+//! keeps the encoder tiny at the cost of compression ratio, which is an
+//! acceptable trade-off since PNG files produced by `save_png` do not need
+//! to be minimal, just valid.
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59,
+    67, 83, 99, 115, 131, 163, 195, 227, 258
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5,
+    5, 5, 5, 0
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513,
+    769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10,
+    11, 11, 12, 12, 13, 13
+];
+const CODE_LENGTH_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15
+];
+
+/// Reads bits LSB-first out of a byte slice, as required by DEFLATE.
+struct BitReader<'a> {
+    data: &'a [u8],
+    byte_pos: usize,
+    bit_pos: u8
+}
+
+impl<'a> BitReader<'a> {
+    fn new(data: &'a [u8]) -> BitReader<'a> {
+        BitReader { data, byte_pos: 0, bit_pos: 0 }
+    }
+
+    fn read_bit(&mut self) -> Result<u32, String> {
+        let byte = *self.data.get(self.byte_pos)
+            .ok_or_else(|| "Inflate: unexpected end of stream".to_string())?;
+
+        let bit = (byte >> self.bit_pos) & 1;
+
+        self.bit_pos += 1;
+        if self.bit_pos == 8 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+
+        Ok(bit as u32)
+    }
+
+    fn read_bits(&mut self, n: u8) -> Result<u32, String> {
+        let mut value = 0u32;
+
+        for i in 0..n {
+            value |= self.read_bit()? << i;
+        }
+
+        Ok(value)
+    }
+
+    fn align_to_byte(&mut self) {
+        if self.bit_pos != 0 {
+            self.bit_pos = 0;
+            self.byte_pos += 1;
+        }
+    }
+
+    fn read_u8(&mut self) -> Result<u8, String> {
+        let byte = *self.data.get(self.byte_pos)
+            .ok_or_else(|| "Inflate: unexpected end of stream".to_string())?;
+        self.byte_pos += 1;
+        Ok(byte)
+    }
+}
+
+/// A canonical Huffman decode table built from per-symbol code lengths, as
+/// described by RFC 1951 section 3.2.2.
+struct Huffman {
+    count: [u16; 16],
+    symbol: Vec<u16>
+}
+
+impl Huffman {
+    fn build(lengths: &[u8]) -> Result<Huffman, String> {
+        let mut count = [0u16; 16];
+
+        for &len in lengths {
+            count[len as usize] += 1;
+        }
+        count[0] = 0;
+
+        let mut left: i32 = 1;
+        for &c in count.iter().skip(1) {
+            left <<= 1;
+            left -= c as i32;
+            if left < 0 {
+                return Err("Inflate: over-subscribed Huffman code".to_string());
+            }
+        }
+
+        let mut offsets = [0u16; 16];
+        for len in 1..15 {
+            offsets[len + 1] = offsets[len] + count[len];
+        }
+
+        let mut symbol = vec![0u16; lengths.len()];
+        for (sym, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbol[offsets[len as usize] as usize] = sym as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+
+        Ok(Huffman { count, symbol })
+    }
+
+    fn decode(&self, reader: &mut BitReader) -> Result<u16, String> {
+        let mut code: i32 = 0;
+        let mut first: i32 = 0;
+        let mut index: i32 = 0;
+
+        for len in 1..16 {
+            code |= reader.read_bit()? as i32;
+            let count = self.count[len] as i32;
+
+            if code - first < count {
+                return Ok(self.symbol[(index + (code - first)) as usize]);
+            }
+
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+
+        Err("Inflate: invalid Huffman code".to_string())
+    }
+}
+
+fn fixed_tables() -> Result<(Huffman, Huffman), String> {
+    let mut lit_lengths = [0u8; 288];
+    lit_lengths[0..144].fill(8);
+    lit_lengths[144..256].fill(9);
+    lit_lengths[256..280].fill(7);
+    lit_lengths[280..288].fill(8);
+
+    let dist_lengths = [5u8; 30];
+
+    Ok((Huffman::build(&lit_lengths)?, Huffman::build(&dist_lengths)?))
+}
+
+fn dynamic_tables(reader: &mut BitReader) -> Result<(Huffman, Huffman), String> {
+    let hlit = reader.read_bits(5)? as usize + 257;
+    let hdist = reader.read_bits(5)? as usize + 1;
+    let hclen = reader.read_bits(4)? as usize + 4;
+
+    let mut cl_lengths = [0u8; 19];
+    for i in 0..hclen {
+        cl_lengths[CODE_LENGTH_ORDER[i]] = reader.read_bits(3)? as u8;
+    }
+
+    let cl_tree = Huffman::build(&cl_lengths)?;
+
+    let mut lengths = Vec::with_capacity(hlit + hdist);
+    while lengths.len() < hlit + hdist {
+        let symbol = cl_tree.decode(reader)?;
+
+        match symbol {
+            0..=15 => lengths.push(symbol as u8),
+            16 => {
+                let prev = *lengths.last()
+                    .ok_or_else(|| "Inflate: repeat with no previous length".to_string())?;
+                let repeat = reader.read_bits(2)? + 3;
+                lengths.resize(lengths.len() + repeat as usize, prev);
+            },
+            17 => {
+                let repeat = reader.read_bits(3)? + 3;
+                lengths.resize(lengths.len() + repeat as usize, 0);
+            },
+            18 => {
+                let repeat = reader.read_bits(7)? + 11;
+                lengths.resize(lengths.len() + repeat as usize, 0);
+            },
+            _ => return Err("Inflate: invalid code length symbol".to_string())
+        }
+    }
+
+    let (lit_lengths, dist_lengths) = lengths.split_at(hlit);
+
+    Ok((Huffman::build(lit_lengths)?, Huffman::build(dist_lengths)?))
+}
+
+fn inflate_block(
+    reader: &mut BitReader, lit_tree: &Huffman, dist_tree: &Huffman,
+    out: &mut Vec<u8>
+) -> Result<(), String> {
+    loop {
+        let symbol = lit_tree.decode(reader)?;
+
+        if symbol < 256 {
+            out.push(symbol as u8);
+        }
+        else if symbol == 256 {
+            return Ok(());
+        }
+        else {
+            let idx = (symbol - 257) as usize;
+            if idx >= LENGTH_BASE.len() {
+                return Err("Inflate: invalid length symbol".to_string());
+            }
+
+            let len = LENGTH_BASE[idx] as usize
+                + reader.read_bits(LENGTH_EXTRA[idx])? as usize;
+
+            let dist_symbol = dist_tree.decode(reader)? as usize;
+            if dist_symbol >= DIST_BASE.len() {
+                return Err("Inflate: invalid distance symbol".to_string());
+            }
+
+            let dist = DIST_BASE[dist_symbol] as usize
+                + reader.read_bits(DIST_EXTRA[dist_symbol])? as usize;
+
+            if dist > out.len() {
+                return Err("Inflate: distance too far back".to_string());
+            }
+
+            let start = out.len() - dist;
+            for i in 0..len {
+                let byte = out[start + i];
+                out.push(byte);
+            }
+        }
+    }
+}
+
+/// Inflate a raw DEFLATE stream (no zlib/gzip wrapper).
+fn inflate_raw(data: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = BitReader::new(data);
+    let mut out = Vec::new();
+
+    loop {
+        let is_final = reader.read_bit()? == 1;
+        let block_type = reader.read_bits(2)?;
+
+        match block_type {
+            0 => {
+                reader.align_to_byte();
+                let len_lo = reader.read_u8()? as u16;
+                let len_hi = reader.read_u8()? as u16;
+                let len = len_lo | (len_hi << 8);
+                let _nlen_lo = reader.read_u8()?;
+                let _nlen_hi = reader.read_u8()?;
+
+                for _ in 0..len {
+                    out.push(reader.read_u8()?);
+                }
+            },
+            1 => {
+                let (lit_tree, dist_tree) = fixed_tables()?;
+                inflate_block(&mut reader, &lit_tree, &dist_tree, &mut out)?;
+            },
+            2 => {
+                let (lit_tree, dist_tree) = dynamic_tables(&mut reader)?;
+                inflate_block(&mut reader, &lit_tree, &dist_tree, &mut out)?;
+            },
+            _ => return Err("Inflate: invalid block type".to_string())
+        }
+
+        if is_final { break; }
+    }
+
+    Ok(out)
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD_ADLER: u32 = 65521;
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+
+    for &byte in data {
+        a = (a + byte as u32) % MOD_ADLER;
+        b = (b + a) % MOD_ADLER;
+    }
+
+    (b << 16) | a
+}
+
+/// Decompress a zlib stream (RFC 1950 header + DEFLATE body + Adler-32
+/// trailer), as used for PNG `IDAT` chunk data.
+pub fn inflate(data: &[u8]) -> Result<Vec<u8>, String> {
+    if data.len() < 6 {
+        return Err("Inflate: zlib stream too short".to_string());
+    }
+
+    let cmf = data[0];
+    if cmf & 0x0f != 8 {
+        return Err("Inflate: unsupported zlib compression method".to_string());
+    }
+
+    let body = &data[2..data.len() - 4];
+    let out = inflate_raw(body)?;
+
+    let expected = adler32(&out);
+    let stored = u32::from_be_bytes([
+        data[data.len() - 4], data[data.len() - 3],
+        data[data.len() - 2], data[data.len() - 1]
+    ]);
+
+    if expected != stored {
+        return Err("Inflate: Adler-32 checksum mismatch".to_string());
+    }
+
+    Ok(out)
+}
+
+/// Compress `data` into a valid zlib stream using only stored (uncompressed)
+/// DEFLATE blocks.
+pub fn deflate_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len() + data.len() / 65535 * 5 + 11);
+
+    // zlib header: CMF = deflate, 32K window; FLG chosen so the 16-bit
+    // header is a multiple of 31 as RFC 1950 requires.
+    out.push(0x78);
+    out.push(0x01);
+
+    if data.is_empty() {
+        out.push(0x01); // BFINAL=1, BTYPE=00, rest of byte is padding
+        out.extend_from_slice(&0u16.to_le_bytes());
+        out.extend_from_slice(&(!0u16).to_le_bytes());
+    }
+    else {
+        let mut offset = 0;
+        while offset < data.len() {
+            let chunk_len = std::cmp::min(65535, data.len() - offset);
+            let is_last = offset + chunk_len == data.len();
+
+            out.push(if is_last { 0x01 } else { 0x00 });
+            out.extend_from_slice(&(chunk_len as u16).to_le_bytes());
+            out.extend_from_slice(&(!(chunk_len as u16)).to_le_bytes());
+            out.extend_from_slice(&data[offset..offset + chunk_len]);
+
+            offset += chunk_len;
+        }
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+
+    out
+}
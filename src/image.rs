@@ -1,12 +1,42 @@
 
 /// Trait used to limit data types for images
-pub trait ImageDataType: Clone + From<u8> + Copy {}
+pub trait ImageDataType: Clone + From<u8> + Copy {
+
+    /// Convert a channel value to `f64` for arithmetic (color conversion,
+    /// filtering, sampling, ...)
+    fn to_f64(self) -> f64;
+
+    /// Build a channel value back from an `f64`, rounding and clamping into
+    /// this type's valid range for integer types, or converting exactly for
+    /// float types
+    fn from_f64(val: f64) -> Self;
+
+    /// The maximum representable channel value: the integer max for
+    /// integer types, or `1.0` for float types
+    fn max_value() -> Self;
+}
 
 // Types supported for the image data are u8, u16, f32, and f64
-impl ImageDataType for u8 {}
-impl ImageDataType for u16 {}
-impl ImageDataType for f32 {}
-impl ImageDataType for f64 {}
+impl ImageDataType for u8 {
+    fn to_f64(self) -> f64 { self as f64 }
+    fn from_f64(val: f64) -> Self { val.round().clamp(0.0, u8::MAX as f64) as u8 }
+    fn max_value() -> Self { u8::MAX }
+}
+impl ImageDataType for u16 {
+    fn to_f64(self) -> f64 { self as f64 }
+    fn from_f64(val: f64) -> Self { val.round().clamp(0.0, u16::MAX as f64) as u16 }
+    fn max_value() -> Self { u16::MAX }
+}
+impl ImageDataType for f32 {
+    fn to_f64(self) -> f64 { self as f64 }
+    fn from_f64(val: f64) -> Self { val as f32 }
+    fn max_value() -> Self { 1.0 }
+}
+impl ImageDataType for f64 {
+    fn to_f64(self) -> f64 { self }
+    fn from_f64(val: f64) -> Self { val }
+    fn max_value() -> Self { 1.0 }
+}
 
 /// Basic representation of an image.
 pub struct Image<T> where
@@ -34,7 +64,7 @@ pub struct Image<T> where
     data: Vec<T>
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Copy)]
 pub enum ColorType {
     Mono,
     RGB,
@@ -51,9 +81,70 @@ impl std::fmt::Display for ColorType {
     }
 }
 
+/// A single image pixel: a fixed-length run of channel values along with
+/// the color type those channels represent.
+///
+/// Implemented for `[T]` so that the channel slices yielded by
+/// [`Image::pixels`]/[`Image::pixels_mut`]/[`Image::enumerate_pixels`] can be
+/// used generically without callers having to re-derive the channel count
+/// or color type from `depth` themselves.
+pub trait Pixel<T: ImageDataType> {
+    /// Number of channels making up this pixel
+    fn channel_count(&self) -> u8;
+
+    /// The channel values that make up this pixel
+    fn channels(&self) -> &[T];
+
+    /// The color type represented by this pixel's channel count
+    fn color_type(&self) -> Result<ColorType, String>;
+}
+
+impl<T: ImageDataType> Pixel<T> for [T] {
+    fn channel_count(&self) -> u8 { self.len() as u8 }
+
+    fn channels(&self) -> &[T] { self }
+
+    fn color_type(&self) -> Result<ColorType, String> {
+        match self.len() {
+            1 => Ok(ColorType::Mono),
+            3 => Ok(ColorType::RGB),
+            4 => Ok(ColorType::RGBA),
+            n => Err(format!("No ColorType with {} channels", n))
+        }
+    }
+}
+
 impl<T> Image<T> where
     T: ImageDataType {
 
+    /// Construct an image directly from its raw parts.
+    ///
+    /// `data` must already be organised in the flat, row-major layout
+    /// documented on the `data` field above and its length must match
+    /// `cols * rows * depth`. Intended for other crate modules (codecs,
+    /// filters, ...) that build pixel data outside of `Image::new` /
+    /// `Image::set_pixel_data`.
+    pub(crate) fn from_raw(
+        cols: u32, rows: u32, depth: u8, color_type: ColorType, data: Vec<T>
+    ) -> Image<T> {
+        Image { cols, rows, depth, color_type, data }
+    }
+
+    /// Number of columns in the image
+    pub fn cols(&self) -> u32 { self.cols }
+
+    /// Number of rows in the image
+    pub fn rows(&self) -> u32 { self.rows }
+
+    /// The color depth (channel count) of the image
+    pub fn depth(&self) -> u8 { self.depth }
+
+    /// The color type of the image
+    pub fn color_type(&self) -> &ColorType { &self.color_type }
+
+    /// The flat, row-major pixel data backing the image
+    pub(crate) fn data(&self) -> &[T] { &self.data }
+
     pub fn new(cols: u32, rows: u32, color_type: ColorType) -> Image<T> {
         match color_type {
             ColorType::Mono =>
@@ -98,11 +189,13 @@ impl<T> Image<T> where
 
         if (col < self.cols) && (row < self.rows) {
 
-            let idx_bot 
-                = (self.depth as u32 * (col * self.cols + row)) as usize;
+            // Row-major addressing, matching the `data` field's documented
+            // layout: data[depth * (row * cols + col) + channel].
+            let idx_bot
+                = (self.depth as u32 * (row * self.cols + col)) as usize;
             let idx_top = idx_bot +  self.depth as usize;
 
-            let pix_data: Vec<T> 
+            let pix_data: Vec<T>
                 = self.data[idx_bot..idx_top].to_vec();
 
             Ok(pix_data)
@@ -124,8 +217,9 @@ impl<T> Image<T> where
         }
 
         if (col < self.cols) && (row < self.rows) {
-            let idx 
-                = (col * self.cols + row) as usize;
+            // Row-major addressing; see get_pixel_data.
+            let idx
+                = (row * self.cols + col) as usize;
 
             Ok(self.data[idx])
         }
@@ -140,8 +234,9 @@ impl<T> Image<T> where
 
         if (col < self.cols) && (row < self.rows) {
 
-            let idx_bot 
-                = (self.depth as u32 * (col * self.cols + row)) as usize;
+            // Row-major addressing; see get_pixel_data.
+            let idx_bot
+                = (self.depth as u32 * (row * self.cols + col)) as usize;
 
             for i in 0..(self.depth as usize) {
                 self.data[i + idx_bot] = val[i];
@@ -155,12 +250,104 @@ impl<T> Image<T> where
                 image is only {}x{}.", col, row, self.cols, self.rows))
         }
     }
+
+    /// Iterate over the image's pixels in row-major order, each yielded as
+    /// its channel slice.
+    pub fn pixels(&self) -> impl Iterator<Item = &[T]> {
+        self.data.chunks_exact(self.depth as usize)
+    }
+
+    /// Iterate mutably over the image's pixels in row-major order, each
+    /// yielded as its channel slice.
+    pub fn pixels_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.data.chunks_exact_mut(self.depth as usize)
+    }
+
+    /// Iterate over the image's pixels in row-major order, each yielded
+    /// alongside its (col, row) coordinate.
+    pub fn enumerate_pixels(&self) -> impl Iterator<Item = (u32, u32, &[T])> {
+        let cols = self.cols;
+
+        self.pixels().enumerate().map(move |(i, px)| {
+            let i = i as u32;
+            (i % cols, i / cols, px)
+        })
+    }
+
+    /// Convert this image to a different [`ColorType`], returning a new
+    /// image.
+    ///
+    /// RGB/RGBA to Mono computes luminance with the Rec.601 weights
+    /// `Y = 0.299*R + 0.587*G + 0.114*B`. Mono to RGB/RGBA replicates the
+    /// single channel across R, G and B. RGB to RGBA appends an opaque
+    /// alpha channel (this type's maximum value); RGBA to RGB drops alpha.
+    pub fn convert(&self, target: ColorType) -> Image<T> {
+        match (&self.color_type, &target) {
+            (ColorType::Mono, ColorType::Mono)
+            | (ColorType::RGB, ColorType::RGB)
+            | (ColorType::RGBA, ColorType::RGBA) =>
+                Image::from_raw(
+                    self.cols, self.rows, self.depth, target, self.data.clone()),
+
+            (ColorType::RGB, ColorType::Mono) | (ColorType::RGBA, ColorType::Mono) => {
+                let data = self.pixels().map(|px| {
+                    let y = 0.299 * px[0].to_f64()
+                        + 0.587 * px[1].to_f64()
+                        + 0.114 * px[2].to_f64();
+                    T::from_f64(y)
+                }).collect();
+
+                Image::from_raw(self.cols, self.rows, 1, ColorType::Mono, data)
+            },
+
+            (ColorType::Mono, ColorType::RGB) | (ColorType::Mono, ColorType::RGBA) => {
+                let with_alpha = target == ColorType::RGBA;
+                let depth = if with_alpha { 4 } else { 3 };
+
+                let mut data = Vec::with_capacity(
+                    (self.cols * self.rows) as usize * depth as usize);
+
+                for px in self.pixels() {
+                    data.push(px[0]);
+                    data.push(px[0]);
+                    data.push(px[0]);
+                    if with_alpha { data.push(T::max_value()); }
+                }
+
+                Image::from_raw(self.cols, self.rows, depth, target, data)
+            },
+
+            (ColorType::RGB, ColorType::RGBA) => {
+                let mut data = Vec::with_capacity(
+                    (self.cols * self.rows) as usize * 4);
+
+                for px in self.pixels() {
+                    data.extend_from_slice(px);
+                    data.push(T::max_value());
+                }
+
+                Image::from_raw(self.cols, self.rows, 4, ColorType::RGBA, data)
+            },
+
+            (ColorType::RGBA, ColorType::RGB) => {
+                let mut data = Vec::with_capacity(
+                    (self.cols * self.rows) as usize * 3);
+
+                for px in self.pixels() {
+                    data.extend_from_slice(&px[0..3]);
+                }
+
+                Image::from_raw(self.cols, self.rows, 3, ColorType::RGB, data)
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
 
     use crate::image::*;
+    use crate::{mono_image, rgb_image, assert_pixels_eq};
 
     #[test]
     fn image_init() {
@@ -183,6 +370,48 @@ mod tests {
         println!("{:?}", img_mono.get_pixel_data(1, 0).unwrap());
         println!("{:?}", img_rgb.get_pixel_data(1, 0).unwrap());
         println!("{}", img_mono.get_pixel_mono(1, 0).unwrap());
-        println!("{}", img_rgb.get_pixel_mono(1, 0).unwrap());
+
+        // get_pixel_mono is only defined for ColorType::Mono images.
+        assert!(img_rgb.get_pixel_mono(1, 0).is_err());
+    }
+
+    #[test]
+    fn pixel_iterators() {
+        let mut img: Image<u8> = Image::new(2, 3, ColorType::RGB);
+
+        for (i, px) in img.pixels_mut().enumerate() {
+            let v = (i * 10) as u8;
+            px.copy_from_slice(&[v, v + 1, v + 2]);
+        }
+
+        assert_eq!(img.pixels().count(), 6);
+        assert_eq!(img.pixels().next().unwrap(), &[0, 1, 2]);
+        assert_eq!(img.pixels().last().unwrap(), &[50, 51, 52]);
+
+        for (col, row, px) in img.enumerate_pixels() {
+            assert_eq!(
+                img.get_pixel_data(col, row).unwrap(), px.to_vec());
+        }
+
+        assert_eq!(
+            [1u8, 2, 3].color_type().unwrap(), ColorType::RGB);
+        assert_eq!([1u8, 2, 3].channel_count(), 3);
+    }
+
+    #[test]
+    fn convert_mono_rgb_round_trip() {
+        let mono = mono_image![128, 0; 0, 0];
+
+        let rgb = mono.convert(ColorType::RGB);
+        assert_pixels_eq!(rgb, rgb_image![(128, 128, 128), (0, 0, 0); (0, 0, 0), (0, 0, 0)]);
+
+        let back_to_mono = rgb.convert(ColorType::Mono);
+        assert_pixels_eq!(back_to_mono, mono);
+
+        let rgba = rgb.convert(ColorType::RGBA);
+        assert_eq!(rgba.get_pixel_data(0, 0).unwrap(), vec![128, 128, 128, 255]);
+
+        let rgb_again = rgba.convert(ColorType::RGB);
+        assert_pixels_eq!(rgb_again, rgb);
     }
 }
\ No newline at end of file
@@ -0,0 +1,7 @@
+mod macros;
+
+pub mod image;
+pub mod codec;
+pub mod filter;
+pub mod view;
+pub mod sample;
@@ -0,0 +1,155 @@
+//! Compile-time image literal macros for tests and ergonomic construction.
+
+/// Build an `Image<u8>` with [`ColorType::Mono`](crate::image::ColorType::Mono)
+/// from a literal grid of channel values: columns are comma-separated,
+/// rows are semicolon-separated.
+///
+/// ```
+/// use cvrs::mono_image;
+///
+/// let img = mono_image![
+///     1, 2, 3;
+///     4, 5, 6
+/// ];
+///
+/// assert_eq!(img.cols(), 3);
+/// assert_eq!(img.rows(), 2);
+/// ```
+#[macro_export]
+macro_rules! mono_image {
+    ( $( $( $val:expr ),+ );+ $(;)? ) => {{
+        let rows: Vec<Vec<u8>> = vec![ $( vec![ $( $val as u8 ),+ ] ),+ ];
+        let row_count = rows.len() as u32;
+        let col_count = rows[0].len() as u32;
+
+        let mut img = $crate::image::Image::<u8>::new(
+            col_count, row_count, $crate::image::ColorType::Mono);
+
+        for (row, values) in rows.into_iter().enumerate() {
+            for (col, val) in values.into_iter().enumerate() {
+                img.set_pixel_data(col as u32, row as u32, vec![val]).unwrap();
+            }
+        }
+
+        img
+    }};
+}
+
+/// Build an `Image<u8>` with [`ColorType::RGB`](crate::image::ColorType::RGB)
+/// from a literal grid of `(r, g, b)` pixels: columns are comma-separated,
+/// rows are semicolon-separated.
+///
+/// ```
+/// use cvrs::rgb_image;
+///
+/// let img = rgb_image![
+///     (1, 2, 3), (4, 5, 6);
+///     (7, 8, 9), (10, 11, 12)
+/// ];
+///
+/// assert_eq!(img.cols(), 2);
+/// assert_eq!(img.rows(), 2);
+/// ```
+#[macro_export]
+macro_rules! rgb_image {
+    ( $( $( ($r:expr, $g:expr, $b:expr) ),+ );+ $(;)? ) => {{
+        let rows: Vec<Vec<(u8, u8, u8)>> = vec![
+            $( vec![ $( ($r as u8, $g as u8, $b as u8) ),+ ] ),+
+        ];
+        let row_count = rows.len() as u32;
+        let col_count = rows[0].len() as u32;
+
+        let mut img = $crate::image::Image::<u8>::new(
+            col_count, row_count, $crate::image::ColorType::RGB);
+
+        for (row, values) in rows.into_iter().enumerate() {
+            for (col, (r, g, b)) in values.into_iter().enumerate() {
+                img.set_pixel_data(col as u32, row as u32, vec![r, g, b]).unwrap();
+            }
+        }
+
+        img
+    }};
+}
+
+/// Assert that two images have the same dimensions and pixel data.
+///
+/// On mismatch, panics reporting the first differing `(col, row)`
+/// coordinate and the two images' channel values there, rather than just
+/// `left != right`.
+#[macro_export]
+macro_rules! assert_pixels_eq {
+    ($left:expr, $right:expr) => {{
+        let left_img = &$left;
+        let right_img = &$right;
+
+        assert_eq!(
+            (left_img.cols(), left_img.rows()),
+            (right_img.cols(), right_img.rows()),
+            "image dimensions differ"
+        );
+
+        for row in 0..left_img.rows() {
+            for col in 0..left_img.cols() {
+                let left_px = left_img.get_pixel_data(col, row).unwrap();
+                let right_px = right_img.get_pixel_data(col, row).unwrap();
+
+                if left_px != right_px {
+                    panic!(
+                        "pixels differ at (col {}, row {}): {:?} vs {:?}",
+                        col, row, left_px, right_px
+                    );
+                }
+            }
+        }
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::image::ColorType;
+
+    #[test]
+    fn mono_image_literal() {
+        let img = mono_image![
+            1, 2, 3;
+            4, 5, 6
+        ];
+
+        assert_eq!(img.cols(), 3);
+        assert_eq!(img.rows(), 2);
+        assert_eq!(*img.color_type(), ColorType::Mono);
+        assert_eq!(img.get_pixel_data(2, 1).unwrap(), vec![6]);
+    }
+
+    #[test]
+    fn rgb_image_literal() {
+        let img = rgb_image![
+            (1, 2, 3), (4, 5, 6);
+            (7, 8, 9), (10, 11, 12)
+        ];
+
+        assert_eq!(img.cols(), 2);
+        assert_eq!(img.rows(), 2);
+        assert_eq!(*img.color_type(), ColorType::RGB);
+        assert_eq!(img.get_pixel_data(1, 1).unwrap(), vec![10, 11, 12]);
+    }
+
+    #[test]
+    fn assert_pixels_eq_passes_for_equal_images() {
+        let a = mono_image![1, 2; 3, 4];
+        let b = mono_image![1, 2; 3, 4];
+
+        assert_pixels_eq!(a, b);
+    }
+
+    #[test]
+    #[should_panic(expected = "pixels differ at (col 1, row 0)")]
+    fn assert_pixels_eq_panics_with_first_mismatch() {
+        let a = mono_image![1, 2; 3, 4];
+        let b = mono_image![1, 9; 3, 4];
+
+        assert_pixels_eq!(a, b);
+    }
+}
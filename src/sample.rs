@@ -0,0 +1,106 @@
+//! Typed, normalized sampling with bilinear interpolation.
+
+use crate::image::{Image, ImageDataType};
+
+impl<T> Image<T> where
+    T: ImageDataType {
+
+    /// Sample the image at fractional, normalized coordinates `(u, v)` in
+    /// `[0.0, 1.0]` using bilinear interpolation across the four
+    /// surrounding pixels.
+    ///
+    /// Channel values are normalized into `[0.0, 1.0]` based on `T`'s
+    /// range (dividing by 255 for `u8`, 65535 for `u16`, passed through for
+    /// float types), so the same call produces consistent output
+    /// regardless of the image's storage type.
+    pub fn sample(&self, u: f64, v: f64) -> Vec<f64> {
+        let cols = self.cols();
+        let rows = self.rows();
+        let depth = self.depth() as usize;
+
+        let x = (u * (cols as f64 - 1.0)).clamp(0.0, cols as f64 - 1.0);
+        let y = (v * (rows as f64 - 1.0)).clamp(0.0, rows as f64 - 1.0);
+
+        let x0 = x.floor() as u32;
+        let y0 = y.floor() as u32;
+        let x1 = (x0 + 1).min(cols - 1);
+        let y1 = (y0 + 1).min(rows - 1);
+
+        let fx = x - x0 as f64;
+        let fy = y - y0 as f64;
+
+        let p00 = self.get_pixel_data(x0, y0).unwrap();
+        let p10 = self.get_pixel_data(x1, y0).unwrap();
+        let p01 = self.get_pixel_data(x0, y1).unwrap();
+        let p11 = self.get_pixel_data(x1, y1).unwrap();
+
+        let max = T::max_value().to_f64();
+
+        (0..depth).map(|c| {
+            let top = p00[c].to_f64() * (1.0 - fx) + p10[c].to_f64() * fx;
+            let bottom = p01[c].to_f64() * (1.0 - fx) + p11[c].to_f64() * fx;
+
+            (top * (1.0 - fy) + bottom * fy) / max
+        }).collect()
+    }
+
+    /// Resize the image to `new_cols x new_rows` using [`Image::sample`].
+    pub fn resize(&self, new_cols: u32, new_rows: u32) -> Image<T> {
+        let depth = self.depth() as usize;
+        let max = T::max_value().to_f64();
+
+        let mut data = Vec::with_capacity(new_cols as usize * new_rows as usize * depth);
+
+        for row in 0..new_rows {
+            let v = if new_rows > 1 { row as f64 / (new_rows as f64 - 1.0) } else { 0.0 };
+
+            for col in 0..new_cols {
+                let u = if new_cols > 1 { col as f64 / (new_cols as f64 - 1.0) } else { 0.0 };
+
+                for channel in self.sample(u, v) {
+                    data.push(T::from_f64(channel * max));
+                }
+            }
+        }
+
+        Image::from_raw(new_cols, new_rows, self.depth(), *self.color_type(), data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::image::ColorType;
+    use crate::mono_image;
+
+    #[test]
+    fn sample_corners_match_source_pixels() {
+        let img = mono_image![0, 255; 255, 0];
+
+        assert_eq!(img.sample(0.0, 0.0), vec![0.0]);
+        assert_eq!(img.sample(1.0, 0.0), vec![1.0]);
+        assert_eq!(img.sample(0.0, 1.0), vec![1.0]);
+        assert_eq!(img.sample(1.0, 1.0), vec![0.0]);
+    }
+
+    #[test]
+    fn sample_interpolates_between_pixels() {
+        let img = mono_image![0, 255];
+
+        let mid = img.sample(0.5, 0.0);
+        assert!((mid[0] - 0.5).abs() < 0.01);
+    }
+
+    #[test]
+    fn resize_preserves_corner_pixels() {
+        let img: crate::image::Image<u8> = mono_image![0, 64; 128, 255];
+
+        let resized = img.resize(4, 4);
+        assert_eq!(resized.cols(), 4);
+        assert_eq!(resized.rows(), 4);
+        assert_eq!(*resized.color_type(), ColorType::Mono);
+
+        assert_eq!(resized.get_pixel_data(0, 0).unwrap(), vec![0]);
+        assert_eq!(resized.get_pixel_data(3, 3).unwrap(), vec![255]);
+    }
+}
@@ -0,0 +1,174 @@
+//! Cropping and borrowed sub-image views (regions of interest).
+
+use crate::image::{ColorType, Image, ImageDataType};
+
+impl<T> Image<T> where
+    T: ImageDataType {
+
+    /// Copy a rectangular region of this image into a new, owned image.
+    pub fn crop(&self, col: u32, row: u32, width: u32, height: u32) -> Result<Image<T>, String> {
+        if col + width > self.cols() || row + height > self.rows() {
+            return Err(format!(
+                "Out of bounds: Cannot crop ({}, {}, {}x{}) from a {}x{} image.",
+                col, row, width, height, self.cols(), self.rows()));
+        }
+
+        let depth = self.depth() as usize;
+        let data = self.data();
+        let cols = self.cols() as usize;
+
+        let mut out = Vec::with_capacity(width as usize * height as usize * depth);
+
+        for r in 0..height as usize {
+            let row_start = depth * ((row as usize + r) * cols + col as usize);
+            let row_end = row_start + width as usize * depth;
+            out.extend_from_slice(&data[row_start..row_end]);
+        }
+
+        Ok(Image::from_raw(width, height, self.depth(), *self.color_type(), out))
+    }
+
+    /// Borrow a rectangular region of this image without copying its data.
+    pub fn view(&self, col: u32, row: u32, width: u32, height: u32) -> Result<ImageView<'_, T>, String> {
+        if col + width > self.cols() || row + height > self.rows() {
+            return Err(format!(
+                "Out of bounds: Cannot view ({}, {}, {}x{}) from a {}x{} image.",
+                col, row, width, height, self.cols(), self.rows()));
+        }
+
+        Ok(ImageView {
+            data: self.data(),
+            parent_row_stride: self.depth() as u32 * self.cols(),
+            col,
+            row,
+            width,
+            height,
+            depth: self.depth(),
+            color_type: *self.color_type()
+        })
+    }
+}
+
+/// A borrowed, rectangular window onto an [`Image`], exposing the same
+/// pixel-access API without copying the parent's data.
+///
+/// Tracks the parent's row stride (`depth * cols`) separately from its own
+/// `width` so that pixel addressing stays correct when the view is
+/// narrower than the image it borrows from.
+pub struct ImageView<'a, T> where
+    T: ImageDataType {
+
+    data: &'a [T],
+    parent_row_stride: u32,
+    col: u32,
+    row: u32,
+    width: u32,
+    height: u32,
+    depth: u8,
+    color_type: ColorType
+}
+
+impl<'a, T> ImageView<'a, T> where
+    T: ImageDataType {
+
+    /// Number of columns in the view
+    pub fn cols(&self) -> u32 { self.width }
+
+    /// Number of rows in the view
+    pub fn rows(&self) -> u32 { self.height }
+
+    /// The color depth (channel count) of the view
+    pub fn depth(&self) -> u8 { self.depth }
+
+    /// The color type of the view
+    pub fn color_type(&self) -> &ColorType { &self.color_type }
+
+    fn pixel_offset(&self, col: u32, row: u32) -> usize {
+        let abs_row = self.row + row;
+        let abs_col = self.col + col;
+
+        (self.parent_row_stride * abs_row + self.depth as u32 * abs_col) as usize
+    }
+
+    /// Get the data for a particular pixel, addressed relative to this
+    /// view's own top-left corner.
+    pub fn get_pixel_data(&self, col: u32, row: u32) -> Result<Vec<T>, String> {
+        if (col < self.width) && (row < self.height) {
+            let idx_bot = self.pixel_offset(col, row);
+            let idx_top = idx_bot + self.depth as usize;
+
+            Ok(self.data[idx_bot..idx_top].to_vec())
+        }
+        else {
+            Err(format!(
+                "Out of bounds: Cannot get pixel data for ({}, {}) since the \
+                view is only {}x{}.", col, row, self.width, self.height))
+        }
+    }
+
+    /// Iterate over the view's pixels in row-major order, each yielded as
+    /// its channel slice.
+    pub fn pixels(&self) -> impl Iterator<Item = &[T]> {
+        let depth = self.depth as u32;
+        let row_stride = self.parent_row_stride;
+        let col0 = self.col;
+        let row0 = self.row;
+        let width = self.width;
+        let data = self.data;
+
+        (0..self.height).flat_map(move |r| {
+            let row_start = (row_stride * (row0 + r) + depth * col0) as usize;
+            let row_end = row_start + (width * depth) as usize;
+
+            data[row_start..row_end].chunks_exact(depth as usize)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use crate::image::ColorType;
+    use crate::mono_image;
+
+    #[test]
+    fn crop_copies_region() {
+        let img = mono_image![
+            1, 2, 3;
+            4, 5, 6;
+            7, 8, 9
+        ];
+
+        let cropped = img.crop(1, 1, 2, 2).unwrap();
+        assert_eq!(cropped.cols(), 2);
+        assert_eq!(cropped.rows(), 2);
+        assert_eq!(cropped.get_pixel_data(0, 0).unwrap(), vec![5]);
+        assert_eq!(cropped.get_pixel_data(1, 1).unwrap(), vec![9]);
+    }
+
+    #[test]
+    fn crop_rejects_out_of_bounds() {
+        let img = mono_image![1, 2; 3, 4];
+        assert!(img.crop(1, 0, 2, 1).is_err());
+    }
+
+    #[test]
+    fn view_addresses_narrower_than_source() {
+        let img = mono_image![
+            1, 2, 3;
+            4, 5, 6;
+            7, 8, 9
+        ];
+
+        let view = img.view(1, 0, 2, 2).unwrap();
+        assert_eq!(view.cols(), 2);
+        assert_eq!(view.rows(), 2);
+        assert_eq!(*view.color_type(), ColorType::Mono);
+
+        assert_eq!(view.get_pixel_data(0, 0).unwrap(), vec![2]);
+        assert_eq!(view.get_pixel_data(1, 1).unwrap(), vec![6]);
+
+        let collected: Vec<Vec<u8>> = view.pixels().map(|p| p.to_vec()).collect();
+        assert_eq!(collected, vec![vec![2], vec![3], vec![5], vec![6]]);
+    }
+}